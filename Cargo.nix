@@ -6909,12 +6909,21 @@ item that gets emitted.
         edition = "2021";
         src = lib.cleanSourceWith { filter = sourceFilter;  src = ./common; };
         dependencies = [
+          {
+            name = "anyhow";
+            packageId = "anyhow";
+            usesDefaultFeatures = false;
+          }
           {
             name = "serde";
             packageId = "serde";
             usesDefaultFeatures = false;
             features = [ "derive" ];
           }
+          {
+            name = "toml";
+            packageId = "toml 0.8.23";
+          }
         ];
 
       };
@@ -7140,6 +7149,10 @@ heapless no_std (i.e. embedded) support
             name = "async-trait";
             packageId = "async-trait";
           }
+          {
+            name = "axum";
+            packageId = "axum";
+          }
           {
             name = "common";
             packageId = "common";
@@ -7172,10 +7185,14 @@ heapless no_std (i.e. embedded) support
             name = "storage-proof-core";
             packageId = "storage-proof-core";
           }
+          {
+            name = "subtle";
+            packageId = "subtle";
+          }
           {
             name = "tokio";
             packageId = "tokio";
-            features = [ "macros" "rt-multi-thread" ];
+            features = [ "macros" "rt-multi-thread" "net" "signal" "sync" ];
           }
           {
             name = "toml";
@@ -20488,6 +20505,10 @@ equation coefficients
             packageId = "anyhow";
             usesDefaultFeatures = false;
           }
+          {
+            name = "async-trait";
+            packageId = "async-trait";
+          }
           {
             name = "bech32";
             packageId = "bech32 0.11.0";
@@ -20505,6 +20526,10 @@ equation coefficients
             name = "common";
             packageId = "common";
           }
+          {
+            name = "cosmrs";
+            packageId = "cosmrs";
+          }
           {
             name = "cosmwasm-std";
             packageId = "cosmwasm-std";
@@ -20539,11 +20564,21 @@ equation coefficients
             usesDefaultFeatures = false;
             features = [ "derive" ];
           }
+          {
+            name = "serde_json";
+            packageId = "serde_json";
+            usesDefaultFeatures = false;
+            features = [ "alloc" ];
+          }
           {
             name = "sp1-sdk";
             packageId = "sp1-sdk";
             usesDefaultFeatures = false;
           }
+          {
+            name = "tempfile";
+            packageId = "tempfile";
+          }
           {
             name = "tokio";
             packageId = "tokio";
@@ -20553,6 +20588,11 @@ equation coefficients
             name = "toml";
             packageId = "toml 0.8.23";
           }
+          {
+            name = "tonic";
+            packageId = "tonic 0.12.3";
+            usesDefaultFeatures = false;
+          }
           {
             name = "valence-authorization-utils";
             packageId = "valence-authorization-utils";
@@ -26649,6 +26689,13 @@ associated AlgorithmIdentifiers (i.e. OIDs)
             optional = true;
           }
         ];
+        devDependencies = [
+          {
+            name = "storage-proof-core";
+            packageId = "storage-proof-core";
+            features = [ "test-fixtures" ];
+          }
+        ];
         features = {
           "circuit" = [ "dep:sp1-build" ];
         };
@@ -26739,6 +26786,12 @@ associated AlgorithmIdentifiers (i.e. OIDs)
             packageId = "anyhow";
             usesDefaultFeatures = false;
           }
+          {
+            name = "bech32";
+            packageId = "bech32 0.11.0";
+            usesDefaultFeatures = false;
+            features = [ "alloc" ];
+          }
           {
             name = "serde";
             packageId = "serde";
@@ -26763,7 +26816,10 @@ associated AlgorithmIdentifiers (i.e. OIDs)
             packageId = "hex";
           }
         ];
-
+        features = {
+          "test-fixtures" = [ ];
+        };
+        resolvedDefaultFeatures = [ "test-fixtures" ];
       };
       "strsim" = rec {
         crateName = "strsim";
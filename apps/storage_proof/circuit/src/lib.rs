@@ -1,7 +1,10 @@
+use alloy_primitives::Address;
 use alloy_rpc_types_eth::EIP1186AccountProofResponse;
+use anyhow::ensure;
+use serde_json::{json, Value};
 
-use storage_proof_core::consts::CW20_ADDR;
-use storage_proof_core::proof::verify_proof;
+use storage_proof_core::consts::{CW20_ADDR, ZK_MINT_CW20_REGISTRY};
+use storage_proof_core::proof::verify_balance_proof;
 use valence_coprocessor::Witness;
 
 use cosmwasm_std::{to_json_binary, Uint128};
@@ -15,41 +18,88 @@ use valence_authorization_utils::{
 };
 
 pub fn circuit(witnesses: Vec<Witness>) -> anyhow::Result<Vec<u8>> {
-    assert!(
-        witnesses.len() == 2,
-        "Expected 2 witnesses: account state proof and neutron addr"
+    ensure!(
+        witnesses.len() == 5,
+        "expected 5 witnesses: account state proof, neutron addr, eth addr, erc20 addr and erc20 balances storage index, got {}",
+        witnesses.len()
     );
 
     // extract the witnesses
     let state_proof_bytes = witnesses[0]
         .as_state_proof()
-        .expect("Failed to get state proof bytes");
-    let neutron_addr_bytes = witnesses[1]
-        .as_data()
-        .expect("failed to get neutron addr bytes");
+        .ok_or_else(|| anyhow::anyhow!("witness 0 must be a state proof"))?;
+    let neutron_addr_bytes = data_witness(&witnesses, 1, "neutron addr")?;
+    let eth_addr = Address::try_from(data_witness(&witnesses, 2, "eth addr")?)
+        .map_err(|e| anyhow::anyhow!("invalid eth addr: {e}"))?;
+    let erc20_addr = Address::try_from(data_witness(&witnesses, 3, "erc20 addr")?)
+        .map_err(|e| anyhow::anyhow!("invalid erc20 addr: {e}"))?;
+    let storage_index_bytes = data_witness(&witnesses, 4, "erc20 balances storage index")?;
+    let storage_index = u64::from_be_bytes(
+        storage_index_bytes
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("erc20 balances storage index must be 8 bytes"))?,
+    );
 
     let proof: EIP1186AccountProofResponse = serde_json::from_slice(&state_proof_bytes.proof)
-        .expect("failed to deserialize the proof bytes");
+        .map_err(|e| anyhow::anyhow!("failed to deserialize the proof bytes: {e}"))?;
 
-    verify_proof(&proof).expect("proof verification failed");
+    // the proof is anchored to the state root attested by the coprocessor
+    // domain and must prove the balance of the eth addr: its native balance
+    // for `ACCOUNT_PROOF_STORAGE_INDEX`, its erc20 balance otherwise
+    let evm_balance = verify_balance_proof(
+        &proof,
+        &state_proof_bytes.state_root,
+        eth_addr,
+        erc20_addr,
+        storage_index,
+    )?;
 
     let neutron_addr = core::str::from_utf8(neutron_addr_bytes)
-        .expect("failed to convert neutron addr bytes to str");
+        .map_err(|e| anyhow::anyhow!("neutron addr is not valid utf-8: {e}"))?;
 
-    let evm_balance = proof.storage_proof[0].value;
-    let evm_balance: u128 = match evm_balance.try_into() {
-        Ok(bal) => bal,
-        Err(_) => panic!("U256 -> u128 parsing of evm balance failed ({evm_balance})"),
-    };
+    let evm_balance: u128 = evm_balance
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("evm balance {evm_balance} does not fit into u128"))?;
+
+    // the state proof block number is attested by the coprocessor
+    // domain, so it is the block the zk message is anchored to
+    let block_number = state_proof_bytes.number;
+    ensure!(
+        block_number > 0,
+        "state proof block number must be non-zero"
+    );
 
-    let zk_msg = build_zk_msg(neutron_addr.to_string(), evm_balance);
+    // the registry is embedded into the circuit at build time rather than
+    // supplied by the prover
+    let zk_msg = build_zk_msg(
+        neutron_addr.to_string(),
+        evm_balance,
+        ZK_MINT_CW20_REGISTRY,
+        block_number,
+    );
 
     let zk_msg = serde_json::to_vec(&zk_msg)?;
 
     Ok(zk_msg)
 }
 
-pub fn build_zk_msg(recipient: String, amount: u128) -> ZkMessage {
+/// returns the bytes of the data witness at `index`
+fn data_witness<'a>(
+    witnesses: &'a [Witness],
+    index: usize,
+    name: &str,
+) -> anyhow::Result<&'a [u8]> {
+    witnesses[index]
+        .as_data()
+        .ok_or_else(|| anyhow::anyhow!("witness {index} must be the {name} bytes"))
+}
+
+pub fn build_zk_msg(
+    recipient: String,
+    amount: u128,
+    registry: u64,
+    block_number: u64,
+) -> ZkMessage {
     let mint_cw20_msg = cw20::Cw20ExecuteMsg::Mint {
         recipient,
         amount: Uint128::new(amount),
@@ -86,10 +136,227 @@ pub fn build_zk_msg(recipient: String, amount: u128) -> ZkMessage {
     };
 
     ZkMessage {
-        registry: 0,
-        block_number: 0,
+        registry,
+        block_number,
         domain: Domain::Main,
         authorization_contract: None,
         message,
     }
 }
+
+/// checks that a zk message decoded from the circuit public values targets
+/// the expected registry and is anchored to the expected block
+pub fn validate_zk_message(
+    msg: &ZkMessage,
+    registry_id: u64,
+    block_number: u64,
+) -> anyhow::Result<()> {
+    ensure!(
+        msg.registry == registry_id,
+        "zk message registry {} does not match the expected registry {registry_id}",
+        msg.registry
+    );
+    ensure!(block_number > 0, "zk message block number must be non-zero");
+    ensure!(
+        msg.block_number == block_number,
+        "zk message block number {} does not match the proven block {block_number}",
+        msg.block_number
+    );
+
+    Ok(())
+}
+
+/// length (in bytes) of the co-processor root prefixed to the circuit output
+pub const COPROCESSOR_ROOT_LEN: usize = 32;
+
+/// description of the byte layout of the public values committed by the
+/// circuit, so that consumers can decode them without reading the circuit
+/// source.
+///
+/// the committed bytes are the co-processor root (see `main.rs`) followed
+/// by the json-serialized `ZkMessage` returned by `circuit`. each segment
+/// gives its byte offset and length; a `null` length spans to the end of
+/// the public values.
+pub fn output_schema() -> Value {
+    json!({
+        "title": "storage proof circuit public values",
+        "description": "raw bytes committed by the circuit: the concatenation of the segments below",
+        "segments": [
+            {
+                "name": "coprocessor_root",
+                "description": "root of the co-processor historical commitments the witnesses were validated against",
+                "offset": 0,
+                "length": COPROCESSOR_ROOT_LEN,
+                "encoding": "raw"
+            },
+            {
+                "name": "zk_message",
+                "description": "valence authorization ZkMessage minting the proven balance on the cw20",
+                "offset": COPROCESSOR_ROOT_LEN,
+                "length": null,
+                "encoding": "utf-8 json",
+                "schema": {
+                    "$schema": "https://json-schema.org/draft/2020-12/schema",
+                    "type": "object",
+                    "properties": {
+                        "registry": { "type": "integer", "description": "zk authorization registry id" },
+                        "block_number": { "type": "integer", "description": "ethereum block number the balance was proven at" },
+                        "domain": { "description": "execution domain of the message" },
+                        "authorization_contract": { "type": ["string", "null"] },
+                        "message": { "description": "authorization EnqueueMsgs message carrying the cw20 mint" }
+                    },
+                    "required": ["registry", "block_number", "domain", "message"]
+                }
+            }
+        ]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::B256;
+    use storage_proof_core::fixtures::{EIP_1186_PROOF, STATE_ROOT};
+    use storage_proof_core::ACCOUNT_PROOF_STORAGE_INDEX;
+    use valence_coprocessor::StateProof;
+
+    use super::*;
+
+    const RECIPIENT: &str = "neutron1recipient";
+    const BLOCK_NUMBER: u64 = 22_000_000;
+
+    fn zk_msg(registry: u64, block_number: u64) -> ZkMessage {
+        build_zk_msg(RECIPIENT.to_string(), 100, registry, block_number)
+    }
+
+    #[test]
+    fn test_validate_zk_message() {
+        let msg = zk_msg(ZK_MINT_CW20_REGISTRY, BLOCK_NUMBER);
+
+        validate_zk_message(&msg, ZK_MINT_CW20_REGISTRY, BLOCK_NUMBER).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_validate_zk_message_registry_mismatch() {
+        let msg = zk_msg(ZK_MINT_CW20_REGISTRY + 1, BLOCK_NUMBER);
+
+        validate_zk_message(&msg, ZK_MINT_CW20_REGISTRY, BLOCK_NUMBER).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_validate_zk_message_zero_block() {
+        let msg = zk_msg(ZK_MINT_CW20_REGISTRY, 0);
+
+        validate_zk_message(&msg, ZK_MINT_CW20_REGISTRY, 0).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_validate_zk_message_block_mismatch() {
+        let msg = zk_msg(ZK_MINT_CW20_REGISTRY, BLOCK_NUMBER);
+
+        validate_zk_message(&msg, ZK_MINT_CW20_REGISTRY, BLOCK_NUMBER + 1).unwrap();
+    }
+
+    /// witnesses requesting the native balance of the fixture account,
+    /// proven by the account proof part of the fixture
+    fn witnesses(block_number: u64) -> Vec<Witness> {
+        let state_root: B256 = STATE_ROOT.parse().unwrap();
+        let mut proof: EIP1186AccountProofResponse = serde_json::from_str(EIP_1186_PROOF).unwrap();
+        proof.storage_proof.clear();
+
+        vec![
+            Witness::StateProof(StateProof {
+                domain: Default::default(),
+                payload: Default::default(),
+                proof: serde_json::to_vec(&proof).unwrap(),
+                number: block_number,
+                state_root: state_root.into(),
+            }),
+            Witness::Data(RECIPIENT.as_bytes().to_vec()),
+            Witness::Data(proof.address.to_vec()),
+            Witness::Data(Address::ZERO.to_vec()),
+            Witness::Data(ACCOUNT_PROOF_STORAGE_INDEX.to_be_bytes().to_vec()),
+        ]
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_circuit_zero_block() {
+        circuit(witnesses(0)).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_circuit_storage_index_without_storage_proof() {
+        // an erc20 balance of the fixture contract is requested, but the
+        // proof carries no storage entries
+        let mut witnesses = witnesses(BLOCK_NUMBER);
+        let account = witnesses[2].as_data().unwrap().to_vec();
+        witnesses[3] = Witness::Data(account);
+        witnesses[4] = Witness::Data(9u64.to_be_bytes().to_vec());
+
+        circuit(witnesses).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_circuit_wrong_eth_addr() {
+        let mut witnesses = witnesses(BLOCK_NUMBER);
+        witnesses[2] = Witness::Data(Address::ZERO.to_vec());
+
+        circuit(witnesses).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_circuit_missing_witness() {
+        let mut witnesses = witnesses(BLOCK_NUMBER);
+        witnesses.pop();
+
+        circuit(witnesses).unwrap();
+    }
+
+    #[test]
+    fn test_circuit_output_matches_schema() {
+        let witnesses = witnesses(BLOCK_NUMBER);
+
+        // public values as committed by `main.rs`
+        let coprocessor_root = [7u8; COPROCESSOR_ROOT_LEN];
+        let public_values = [&coprocessor_root[..], &circuit(witnesses).unwrap()].concat();
+
+        let schema = output_schema();
+        let segment = |name: &str| {
+            let segment = schema["segments"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|segment| segment["name"] == name)
+                .unwrap();
+            let offset = segment["offset"].as_u64().unwrap() as usize;
+            let end = segment["length"]
+                .as_u64()
+                .map_or(public_values.len(), |length| offset + length as usize);
+
+            &public_values[offset..end]
+        };
+
+        assert_eq!(segment("coprocessor_root"), &coprocessor_root);
+
+        let msg: ZkMessage = serde_json::from_slice(segment("zk_message")).unwrap();
+        validate_zk_message(&msg, ZK_MINT_CW20_REGISTRY, BLOCK_NUMBER).unwrap();
+
+        // the fixture account holds no native balance
+        let expected = build_zk_msg(
+            RECIPIENT.to_string(),
+            0,
+            ZK_MINT_CW20_REGISTRY,
+            BLOCK_NUMBER,
+        );
+        assert_eq!(
+            serde_json::to_value(&msg).unwrap(),
+            serde_json::to_value(&expected).unwrap()
+        );
+    }
+}
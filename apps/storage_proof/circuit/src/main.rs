@@ -10,6 +10,7 @@ pub fn main() {
     let w = w.validate::<Sp1Hasher>().unwrap();
 
     let r = w.root;
+    assert_eq!(r.len(), storage_proof_circuit::COPROCESSOR_ROOT_LEN);
 
     let b = storage_proof_circuit::circuit(w.witnesses).unwrap();
 
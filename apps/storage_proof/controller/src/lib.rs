@@ -2,7 +2,10 @@ use alloy_primitives::{hex, Address};
 use alloy_rpc_types_eth::EIP1186AccountProofResponse;
 use core::str::FromStr;
 use serde_json::{json, Value};
-use storage_proof_core::{proof::mapping_slot_key, ControllerInputs};
+use storage_proof_core::{
+    proof::{mapping_slot_key, summarize_proof},
+    ControllerInputs, ACCOUNT_PROOF_STORAGE_INDEX,
+};
 use valence_coprocessor::{DomainData, StateProof, Witness};
 use valence_coprocessor_wasm::abi;
 
@@ -32,7 +35,9 @@ pub fn get_witnesses(args: Value) -> anyhow::Result<Vec<Witness>> {
         serde_json::to_string_pretty(&args)?
     )?;
 
-    let witness_inputs: ControllerInputs = serde_json::from_value(args)?;
+    let witness_inputs = ControllerInputs::try_from(args)?;
+    witness_inputs.validate()?;
+
     let erc20_addr = Address::from_str(&witness_inputs.erc20_addr)?;
     let eth_addr = Address::from_str(&witness_inputs.eth_addr)?;
 
@@ -44,18 +49,30 @@ pub fn get_witnesses(args: Value) -> anyhow::Result<Vec<Witness>> {
 
     let block_number_hex = format!("{:#x}", block.number);
 
-    let slot_key = mapping_slot_key(eth_addr, witness_inputs.erc20_balances_map_storage_index);
-
-    abi::log!("storage key = {}", format!("{slot_key:#x}"))?;
-
-    let proof = abi::alchemy(
-        NETWORK,
-        "eth_getProof",
-        &json!([erc20_addr, [slot_key], block_number_hex]),
-    )?;
+    let proof = if witness_inputs.erc20_balances_map_storage_index == ACCOUNT_PROOF_STORAGE_INDEX {
+        // native balance of the eth address itself: the account proof
+        // alone is sufficient, no storage slots are requested
+        abi::log!("requesting account proof for {eth_addr}")?;
+
+        abi::alchemy(
+            NETWORK,
+            "eth_getProof",
+            &json!([eth_addr, [], block_number_hex]),
+        )?
+    } else {
+        let slot_key = mapping_slot_key(eth_addr, witness_inputs.erc20_balances_map_storage_index);
+
+        abi::log!("storage key = {}", format!("{slot_key:#x}"))?;
+
+        abi::alchemy(
+            NETWORK,
+            "eth_getProof",
+            &json!([erc20_addr, [slot_key], block_number_hex]),
+        )?
+    };
 
     let proof: EIP1186AccountProofResponse = serde_json::from_value(proof)?;
-    abi::log!("proof: {}", serde_json::to_string_pretty(&proof)?)?;
+    abi::log!("proof: {}", summarize_proof(&proof, &state_root))?;
     let proof = serde_json::to_vec(&proof)?;
 
     let state_proof = StateProof {
@@ -71,6 +88,17 @@ pub fn get_witnesses(args: Value) -> anyhow::Result<Vec<Witness>> {
         Witness::StateProof(state_proof),
         // witness 1: neutron addr (destination)
         Witness::Data(witness_inputs.neutron_addr.as_bytes().to_vec()),
+        // witness 2: eth addr whose balance is proven
+        Witness::Data(eth_addr.to_vec()),
+        // witness 3: erc20 addr holding the balances mapping
+        Witness::Data(erc20_addr.to_vec()),
+        // witness 4: erc20 balances mapping storage index
+        Witness::Data(
+            witness_inputs
+                .erc20_balances_map_storage_index
+                .to_be_bytes()
+                .to_vec(),
+        ),
     ]
     .to_vec())
 }
@@ -1,2 +1,3 @@
 // DO NOT EDIT. This file is automatically generated by deploy_coprocessor_app.rs.
-pub const CW20_ADDR: &str = "neutron1y29h7gpmfcj4ujmatv5wrqvyxtwy2556uh6038htz8xr53z4ah4s3exmj5";
\ No newline at end of file
+pub const CW20_ADDR: &str = "neutron1y29h7gpmfcj4ujmatv5wrqvyxtwy2556uh6038htz8xr53z4ah4s3exmj5";
+pub const ZK_MINT_CW20_REGISTRY: u64 = 0;
\ No newline at end of file
@@ -0,0 +1,8 @@
+//! mainnet proof fixtures shared with the tests of the crates built on
+//! top of this one. enabled by the `test-fixtures` feature.
+
+/// `eth_getProof` response for a usdc balances mapping slot
+pub const EIP_1186_PROOF: &str = include_str!("../fixtures/eip1186_proof.json");
+
+/// state root of the mainnet block the proof above was fetched at
+pub const STATE_ROOT: &str = "0xdc278759c0b1080617507b02c8688e3aa64139fbb16267d79a740824299b12b0";
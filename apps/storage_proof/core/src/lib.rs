@@ -3,8 +3,24 @@
 extern crate alloc;
 
 pub mod consts;
+#[cfg(any(test, feature = "test-fixtures"))]
+pub mod fixtures;
 pub mod proof;
 
+use anyhow::ensure;
+
+/// bech32 human readable part expected for the neutron recipient
+pub const NEUTRON_BECH32_PREFIX: &str = "neutron";
+
+/// upper bound (exclusive) for the erc20 balances mapping slot index.
+/// erc20 contracts declare the balances mapping among their first
+/// storage variables, so anything above this is most likely a mistake.
+pub const MAX_ERC20_BALANCES_STORAGE_INDEX: u64 = 100;
+
+/// sentinel erc20 balances storage index requesting a proof of the
+/// account's native balance (account proof) instead of an erc20 balance
+pub const ACCOUNT_PROOF_STORAGE_INDEX: u64 = u64::MAX;
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct ControllerInputs {
     pub erc20_addr: alloc::string::String,
@@ -12,3 +28,161 @@ pub struct ControllerInputs {
     pub eth_addr: alloc::string::String,
     pub neutron_addr: alloc::string::String,
 }
+
+impl ControllerInputs {
+    /// validates the inputs before they get turned into witnesses so that
+    /// malformed requests fail early instead of deep inside the circuit.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        validate_evm_address(&self.erc20_addr)?;
+        validate_evm_address(&self.eth_addr)?;
+        validate_bech32_address(&self.neutron_addr, NEUTRON_BECH32_PREFIX)?;
+
+        ensure!(
+            self.erc20_balances_map_storage_index < MAX_ERC20_BALANCES_STORAGE_INDEX
+                || self.erc20_balances_map_storage_index == ACCOUNT_PROOF_STORAGE_INDEX,
+            "erc20 balances storage index {} exceeds the maximum of {}",
+            self.erc20_balances_map_storage_index,
+            MAX_ERC20_BALANCES_STORAGE_INDEX - 1
+        );
+
+        Ok(())
+    }
+}
+
+impl TryFrom<serde_json::Value> for ControllerInputs {
+    type Error = anyhow::Error;
+
+    /// deserializes the json proof request arguments
+    fn try_from(args: serde_json::Value) -> anyhow::Result<Self> {
+        serde_json::from_value(args)
+            .map_err(|e| anyhow::anyhow!("failed to deserialize controller inputs: {e}"))
+    }
+}
+
+/// checks that the address is a 0x-prefixed, 20 byte hex string
+pub fn validate_evm_address(addr: &str) -> anyhow::Result<()> {
+    let hex_part = addr
+        .strip_prefix("0x")
+        .ok_or_else(|| anyhow::anyhow!("evm address {addr} is not 0x-prefixed"))?;
+
+    ensure!(
+        addr.len() == 42,
+        "evm address {addr} must be 42 characters long"
+    );
+    ensure!(
+        hex_part.chars().all(|c| c.is_ascii_hexdigit()),
+        "evm address {addr} contains non-hex characters"
+    );
+
+    Ok(())
+}
+
+/// decodes the bech32 address (verifying its checksum) and checks
+/// that its human readable part matches the expected prefix
+pub fn validate_bech32_address(addr: &str, expected_prefix: &str) -> anyhow::Result<()> {
+    let (hrp, _) =
+        bech32::decode(addr).map_err(|e| anyhow::anyhow!("invalid bech32 address {addr}: {e}"))?;
+
+    ensure!(
+        hrp.as_str() == expected_prefix,
+        "address {addr} has prefix {}, expected {expected_prefix}",
+        hrp.as_str()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn valid_inputs() -> ControllerInputs {
+        ControllerInputs {
+            erc20_addr: "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".to_string(),
+            erc20_balances_map_storage_index: 9,
+            eth_addr: "0x37305B1cD40574E4C5Ce33f8e8306Be057fD7341".to_string(),
+            neutron_addr: consts::CW20_ADDR.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_controller_inputs_try_from_json() {
+        let inputs = valid_inputs();
+        let args = serde_json::to_value(&inputs).unwrap();
+
+        let parsed = ControllerInputs::try_from(args).unwrap();
+
+        assert_eq!(parsed.erc20_addr, inputs.erc20_addr);
+        assert_eq!(parsed.neutron_addr, inputs.neutron_addr);
+        assert_eq!(
+            parsed.erc20_balances_map_storage_index,
+            inputs.erc20_balances_map_storage_index
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_controller_inputs_try_from_invalid_json() {
+        ControllerInputs::try_from(serde_json::json!({ "erc20_addr": 1 })).unwrap();
+    }
+
+    #[test]
+    fn test_validate_controller_inputs() {
+        valid_inputs().validate().unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_validate_invalid_erc20_addr() {
+        let mut inputs = valid_inputs();
+        inputs.erc20_addr.pop();
+
+        inputs.validate().unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_validate_unprefixed_eth_addr() {
+        let mut inputs = valid_inputs();
+        inputs.eth_addr = inputs.eth_addr.replace("0x", "00");
+
+        inputs.validate().unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_validate_invalid_neutron_addr_checksum() {
+        let mut inputs = valid_inputs();
+        inputs.neutron_addr.pop();
+        inputs.neutron_addr.push('q');
+
+        inputs.validate().unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_validate_wrong_neutron_addr_prefix() {
+        let mut inputs = valid_inputs();
+        inputs.neutron_addr = "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu".to_string();
+
+        inputs.validate().unwrap();
+    }
+
+    #[test]
+    fn test_validate_account_proof_storage_index() {
+        let mut inputs = valid_inputs();
+        inputs.erc20_balances_map_storage_index = ACCOUNT_PROOF_STORAGE_INDEX;
+
+        inputs.validate().unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_validate_storage_index_out_of_bounds() {
+        let mut inputs = valid_inputs();
+        inputs.erc20_balances_map_storage_index = MAX_ERC20_BALANCES_STORAGE_INDEX;
+
+        inputs.validate().unwrap();
+    }
+}
@@ -1,8 +1,16 @@
-use std::path::{Path, PathBuf};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
 
+use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
 
 pub const ZK_MINT_CW20_LABEL: &str = "zk_mint_cw20";
+pub const ZK_MINT_CW20_REGISTRY: u64 = 0;
+
+/// prefix of the environment variables read into `NeutronStrategyConfig`
+pub const NEUTRON_ENV_PREFIX: &str = "NEUTRON_";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NeutronStrategyConfig {
@@ -20,6 +28,96 @@ pub struct NeutronStrategyConfig {
     pub coprocessor_app_id: String,
 }
 
+impl NeutronStrategyConfig {
+    /// reads the strategy config from a toml file, e.g. the
+    /// `neutron_strategy_config.toml` written by the provisioner
+    pub fn from_toml(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|e| anyhow!("failed to read {}: {e}", path.display()))?;
+
+        toml::from_str(&content)
+            .map_err(|e| anyhow!("failed to parse neutron strategy config: {e}"))
+    }
+
+    /// builds the strategy config purely from `NEUTRON_`-prefixed
+    /// environment variables. every field is required.
+    pub fn from_env() -> anyhow::Result<Self> {
+        EnvOverrides::from_env().into_config()
+    }
+
+    /// applies the overrides that are set on top of the base config
+    pub fn merge(base: Self, overrides: EnvOverrides) -> Self {
+        Self {
+            grpc_url: overrides.grpc_url.unwrap_or(base.grpc_url),
+            grpc_port: overrides.grpc_port.unwrap_or(base.grpc_port),
+            chain_id: overrides.chain_id.unwrap_or(base.chain_id),
+            authorizations: overrides.authorizations.unwrap_or(base.authorizations),
+            processor: overrides.processor.unwrap_or(base.processor),
+            cw20: overrides.cw20.unwrap_or(base.cw20),
+            coprocessor_app_id: overrides
+                .coprocessor_app_id
+                .unwrap_or(base.coprocessor_app_id),
+        }
+    }
+}
+
+/// partial `NeutronStrategyConfig` overrides sourced from the environment.
+/// useful for containerized deployments where the provisioned toml config
+/// is baked into the image but some values (e.g. the node url) differ.
+#[derive(Debug, Clone, Default)]
+pub struct EnvOverrides {
+    pub grpc_url: Option<String>,
+    pub grpc_port: Option<String>,
+    pub chain_id: Option<String>,
+    pub authorizations: Option<String>,
+    pub processor: Option<String>,
+    pub cw20: Option<String>,
+    pub coprocessor_app_id: Option<String>,
+}
+
+impl EnvOverrides {
+    /// collects whichever `NEUTRON_`-prefixed variables are set. the
+    /// variable name is the prefixed, uppercased field name.
+    pub fn from_env() -> Self {
+        Self {
+            grpc_url: read_env_var("grpc_url"),
+            grpc_port: read_env_var("grpc_port"),
+            chain_id: read_env_var("chain_id"),
+            authorizations: read_env_var("authorizations"),
+            processor: read_env_var("processor"),
+            cw20: read_env_var("cw20"),
+            coprocessor_app_id: read_env_var("coprocessor_app_id"),
+        }
+    }
+
+    /// turns the overrides into a full config, failing on the first
+    /// field that is not set
+    pub fn into_config(self) -> anyhow::Result<NeutronStrategyConfig> {
+        Ok(NeutronStrategyConfig {
+            grpc_url: required(self.grpc_url, "grpc_url")?,
+            grpc_port: required(self.grpc_port, "grpc_port")?,
+            chain_id: required(self.chain_id, "chain_id")?,
+            authorizations: required(self.authorizations, "authorizations")?,
+            processor: required(self.processor, "processor")?,
+            cw20: required(self.cw20, "cw20")?,
+            coprocessor_app_id: required(self.coprocessor_app_id, "coprocessor_app_id")?,
+        })
+    }
+}
+
+fn neutron_env_key(field: &str) -> String {
+    format!("{NEUTRON_ENV_PREFIX}{}", field.to_uppercase())
+}
+
+fn read_env_var(field: &str) -> Option<String> {
+    env::var(neutron_env_key(field)).ok()
+}
+
+fn required(value: Option<String>, field: &str) -> anyhow::Result<String> {
+    value.ok_or_else(|| anyhow!("{} is not set", neutron_env_key(field)))
+}
+
 pub fn workspace_dir() -> PathBuf {
     Path::new(env!("CARGO_MANIFEST_DIR"))
         .parent()
@@ -38,3 +136,90 @@ pub fn provisioner_dir() -> PathBuf {
 pub fn zk_apps_dir() -> PathBuf {
     workspace_dir().join("apps")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_config() -> NeutronStrategyConfig {
+        NeutronStrategyConfig {
+            grpc_url: "http://localhost".to_string(),
+            grpc_port: "9090".to_string(),
+            chain_id: "neutron-1".to_string(),
+            authorizations: "neutron1authorizations".to_string(),
+            processor: "neutron1processor".to_string(),
+            cw20: "neutron1cw20".to_string(),
+            coprocessor_app_id: "app".to_string(),
+        }
+    }
+
+    fn full_overrides() -> EnvOverrides {
+        let cfg = base_config();
+        EnvOverrides {
+            grpc_url: Some(cfg.grpc_url),
+            grpc_port: Some(cfg.grpc_port),
+            chain_id: Some(cfg.chain_id),
+            authorizations: Some(cfg.authorizations),
+            processor: Some(cfg.processor),
+            cw20: Some(cfg.cw20),
+            coprocessor_app_id: Some(cfg.coprocessor_app_id),
+        }
+    }
+
+    #[test]
+    fn test_merge_partial_overrides() {
+        let overrides = EnvOverrides {
+            grpc_url: Some("http://override".to_string()),
+            coprocessor_app_id: Some("override_app".to_string()),
+            ..Default::default()
+        };
+
+        let merged = NeutronStrategyConfig::merge(base_config(), overrides);
+
+        assert_eq!(merged.grpc_url, "http://override");
+        assert_eq!(merged.coprocessor_app_id, "override_app");
+        assert_eq!(merged.grpc_port, "9090");
+        assert_eq!(merged.chain_id, "neutron-1");
+        assert_eq!(merged.authorizations, "neutron1authorizations");
+        assert_eq!(merged.processor, "neutron1processor");
+        assert_eq!(merged.cw20, "neutron1cw20");
+    }
+
+    #[test]
+    fn test_merge_no_overrides() {
+        let merged = NeutronStrategyConfig::merge(base_config(), EnvOverrides::default());
+
+        assert_eq!(merged.grpc_url, "http://localhost");
+        assert_eq!(merged.coprocessor_app_id, "app");
+    }
+
+    #[test]
+    fn test_into_config() {
+        let cfg = full_overrides().into_config().unwrap();
+
+        assert_eq!(cfg.grpc_url, "http://localhost");
+        assert_eq!(cfg.coprocessor_app_id, "app");
+    }
+
+    #[test]
+    fn test_into_config_missing_variable() {
+        let overrides = EnvOverrides {
+            processor: None,
+            ..full_overrides()
+        };
+
+        let err = overrides.into_config().unwrap_err();
+
+        assert!(err.to_string().contains("NEUTRON_PROCESSOR"));
+    }
+
+    #[test]
+    fn test_env_overrides_from_env() {
+        // the only test touching the process environment
+        env::set_var("NEUTRON_CHAIN_ID", "neutron-test");
+
+        let overrides = EnvOverrides::from_env();
+
+        assert_eq!(overrides.chain_id.as_deref(), Some("neutron-test"));
+    }
+}
@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use anyhow::ensure;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Router,
+};
+use log::{info, warn};
+use subtle::ConstantTimeEq;
+use tokio::{net::TcpListener, sync::watch};
+
+const ADMIN: &str = "ADMIN";
+
+/// header that must carry the configured `ADMIN_TOKEN`
+pub const ADMIN_TOKEN_HEADER: &str = "x-admin-token";
+
+/// handle for pausing and resuming the coordinator without killing the process.
+/// the coordinator holds a receiver (see `CoordinatorControl::subscribe`) and
+/// waits before each cycle for as long as it is paused.
+#[derive(Clone)]
+pub struct CoordinatorControl {
+    paused_tx: Arc<watch::Sender<bool>>,
+}
+
+impl Default for CoordinatorControl {
+    fn default() -> Self {
+        let (paused_tx, _) = watch::channel(false);
+        Self {
+            paused_tx: Arc::new(paused_tx),
+        }
+    }
+}
+
+impl CoordinatorControl {
+    pub fn pause(&self) {
+        self.paused_tx.send_replace(true);
+    }
+
+    pub fn resume(&self) {
+        self.paused_tx.send_replace(false);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        *self.paused_tx.borrow()
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.paused_tx.subscribe()
+    }
+}
+
+#[derive(Clone)]
+struct AdminState {
+    control: CoordinatorControl,
+    admin_token: String,
+}
+
+/// binds the admin api to `addr` and serves it in a background task.
+/// exposes `POST /admin/pause` and `POST /admin/resume`, both of which
+/// require the `x-admin-token` header to match `admin_token`.
+/// errors if `admin_token` is empty.
+pub async fn spawn_admin_api(
+    addr: &str,
+    control: CoordinatorControl,
+    admin_token: String,
+) -> anyhow::Result<()> {
+    ensure!(!admin_token.is_empty(), "admin token must not be empty");
+
+    let router = Router::new()
+        .route("/admin/pause", post(pause))
+        .route("/admin/resume", post(resume))
+        .with_state(AdminState {
+            control,
+            admin_token,
+        });
+
+    let listener = TcpListener::bind(addr).await?;
+    info!(target: ADMIN, "admin api listening on {}", listener.local_addr()?);
+
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, router).await {
+            warn!(target: ADMIN, "admin api stopped: {e:?}");
+        }
+    });
+
+    Ok(())
+}
+
+async fn pause(State(state): State<AdminState>, headers: HeaderMap) -> StatusCode {
+    if !is_authorized(&headers, &state.admin_token) {
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    info!(target: ADMIN, "pausing the coordinator");
+    state.control.pause();
+
+    StatusCode::OK
+}
+
+async fn resume(State(state): State<AdminState>, headers: HeaderMap) -> StatusCode {
+    if !is_authorized(&headers, &state.admin_token) {
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    info!(target: ADMIN, "resuming the coordinator");
+    state.control.resume();
+
+    StatusCode::OK
+}
+
+fn is_authorized(headers: &HeaderMap, admin_token: &str) -> bool {
+    if admin_token.is_empty() {
+        return false;
+    }
+
+    headers
+        .get(ADMIN_TOKEN_HEADER)
+        .is_some_and(|token| bool::from(token.as_bytes().ct_eq(admin_token.as_bytes())))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    const TOKEN: &str = "secret";
+
+    fn headers_with_token(token: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(ADMIN_TOKEN_HEADER, HeaderValue::from_static(token));
+        headers
+    }
+
+    #[test]
+    fn test_is_authorized() {
+        assert!(is_authorized(&headers_with_token(TOKEN), TOKEN));
+    }
+
+    #[test]
+    fn test_is_authorized_missing_header() {
+        assert!(!is_authorized(&HeaderMap::new(), TOKEN));
+    }
+
+    #[test]
+    fn test_is_authorized_empty_header() {
+        assert!(!is_authorized(&headers_with_token(""), TOKEN));
+    }
+
+    #[test]
+    fn test_is_authorized_wrong_token() {
+        assert!(!is_authorized(&headers_with_token("secreT"), TOKEN));
+        assert!(!is_authorized(&headers_with_token("secret2"), TOKEN));
+    }
+
+    #[test]
+    fn test_is_authorized_empty_admin_token() {
+        assert!(!is_authorized(&headers_with_token(""), ""));
+    }
+}
@@ -1,33 +1,198 @@
-use std::time::Duration;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use common::ZK_MINT_CW20_LABEL;
 use cw20::{BalanceResponse, Cw20QueryMsg};
 use log::info;
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    sync::{watch, Notify},
+};
 use valence_coordinator_sdk::coordinator::ValenceCoordinator;
 use valence_domain_clients::{
     coprocessor::base_client::{Base64, CoprocessorBaseClient},
     cosmos::{grpc_client::GrpcSigningClient, wasm_client::WasmClient},
 };
 
-use crate::strategy::Strategy;
+use crate::strategy::{CoordinatorStrategy, Strategy, Trigger};
 
 const COORDINATOR_LOG_TARGET: &str = "COORDINATOR";
+const SHUTDOWN: &str = "SHUTDOWN";
+
+/// shutdown handle shared between the signal listener, the coordinator
+/// thread and the runner. once a shutdown is requested the coordinator
+/// does not start any new cycle, while a cycle that is already executing
+/// runs to completion before the coordinator reports itself as stopped.
+#[derive(Clone, Default)]
+pub struct GracefulShutdown {
+    requested: Arc<AtomicBool>,
+    stopped: Arc<AtomicBool>,
+    notify: Arc<Notify>,
+}
+
+impl GracefulShutdown {
+    /// spawns a task that requests a shutdown on SIGTERM or SIGINT
+    pub fn listen(&self) -> anyhow::Result<()> {
+        let mut sigterm = signal(SignalKind::terminate())?;
+        let mut sigint = signal(SignalKind::interrupt())?;
+        let shutdown = self.clone();
+
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = sigterm.recv() => info!(target: SHUTDOWN, "received SIGTERM"),
+                _ = sigint.recv() => info!(target: SHUTDOWN, "received SIGINT"),
+            }
+            shutdown.request();
+        });
+
+        Ok(())
+    }
+
+    pub fn request(&self) {
+        self.requested.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    pub fn is_requested(&self) -> bool {
+        self.requested.load(Ordering::SeqCst)
+    }
+
+    /// resolves once a shutdown has been requested
+    pub async fn requested(&self) {
+        self.wait_for(&self.requested).await
+    }
+
+    fn confirm_stopped(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    /// resolves once the coordinator has stopped starting new cycles
+    pub async fn stopped(&self) {
+        self.wait_for(&self.stopped).await
+    }
+
+    async fn wait_for(&self, flag: &AtomicBool) {
+        loop {
+            // register for the notification before checking the flag
+            // so that a concurrent update cannot be missed
+            let notified = self.notify.notified();
+            if flag.load(Ordering::SeqCst) {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// coordinator that drives a `CoordinatorStrategy`. implements the
+/// ValenceCoordinator trait, which defines the main loop and provides
+/// the default implementation for spawning the coordinator.
+pub struct Coordinator<S> {
+    strategy: S,
+    shutdown: GracefulShutdown,
+    /// pause signal driven by `CoordinatorControl`
+    paused_rx: watch::Receiver<bool>,
+    cycle: u64,
+}
+
+impl<S: CoordinatorStrategy> Coordinator<S> {
+    pub fn new(strategy: S, shutdown: GracefulShutdown, paused_rx: watch::Receiver<bool>) -> Self {
+        Self {
+            strategy,
+            shutdown,
+            paused_rx,
+            cycle: 0,
+        }
+    }
+
+    /// runs the strategy specific preparation and then waits for as long
+    /// as the coordinator is paused
+    async fn wait_for_next_cycle(&self) -> anyhow::Result<()> {
+        self.strategy.prepare_cycle().await?;
+
+        let mut paused_rx = self.paused_rx.clone();
+        if *paused_rx.borrow() {
+            info!(target: COORDINATOR_LOG_TARGET, "coordinator paused, waiting to be resumed...");
+            paused_rx.wait_for(|paused| !paused).await?;
+            info!(target: COORDINATOR_LOG_TARGET, "coordinator resumed");
+        }
+
+        Ok(())
+    }
+
+    /// confirms the shutdown and parks the coordinator loop until the
+    /// runner exits the process
+    async fn stop(&self) -> anyhow::Result<()> {
+        info!(target: SHUTDOWN, "{}: shutdown requested, not starting a new cycle", self.strategy.name());
+        self.shutdown.confirm_stopped();
+
+        std::future::pending().await
+    }
+}
 
-// implement the ValenceCoordinator trait for the Strategy struct.
-// This trait defines the main loop of the strategy and inherits
-// the default implementation for spawning the coordinator.
 #[async_trait]
-impl ValenceCoordinator for Strategy {
+impl<S: CoordinatorStrategy + 'static> ValenceCoordinator for Coordinator<S> {
     fn get_name(&self) -> String {
-        format!("Valence Coprocessor App: {}", self.label)
+        self.strategy.name()
     }
 
     async fn cycle(&mut self) -> anyhow::Result<()> {
+        if self.shutdown.is_requested() {
+            return self.stop().await;
+        }
+
+        // waiting for the next cycle (including while paused) is interrupted
+        // by a shutdown request, the execution of the cycle itself is not
+        let shutdown_requested = tokio::select! {
+            res = self.wait_for_next_cycle() => {
+                res?;
+                false
+            }
+            _ = self.shutdown.requested() => true,
+        };
+        if shutdown_requested {
+            return self.stop().await;
+        }
+
+        let trigger = Trigger { cycle: self.cycle };
+        self.cycle += 1;
+
+        if !self.strategy.should_process(&trigger) {
+            info!(target: COORDINATOR_LOG_TARGET, "{}: skipping cycle {}", self.get_name(), trigger.cycle);
+            return Ok(());
+        }
+
+        self.strategy.execute(trigger).await
+    }
+}
+
+#[async_trait]
+impl CoordinatorStrategy for Strategy {
+    fn name(&self) -> String {
+        format!("Valence Coprocessor App: {}", self.label)
+    }
+
+    async fn prepare_cycle(&self) -> anyhow::Result<()> {
         info!(target: COORDINATOR_LOG_TARGET, "sleeping for {}sec...", self.timeout);
         tokio::time::sleep(Duration::from_secs(self.timeout)).await;
 
-        info!(target: COORDINATOR_LOG_TARGET, "{}: Starting cycle...", self.get_name());
+        Ok(())
+    }
+
+    fn should_process(&self, _trigger: &Trigger) -> bool {
+        // every cycle submits a fresh proof request
+        true
+    }
+
+    async fn execute(&self, trigger: Trigger) -> anyhow::Result<()> {
+        info!(target: COORDINATOR_LOG_TARGET, "{}: Starting cycle {}...", self.name(), trigger.cycle);
 
         let ntrn_addr = self
             .neutron_client
@@ -95,3 +260,101 @@ impl ValenceCoordinator for Strategy {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicU64;
+
+    use super::*;
+
+    /// strategy without any chain interaction that records its executions
+    #[derive(Default)]
+    struct NoopStrategy {
+        skip: bool,
+        executed: AtomicU64,
+    }
+
+    #[async_trait]
+    impl CoordinatorStrategy for NoopStrategy {
+        fn name(&self) -> String {
+            "noop".to_string()
+        }
+
+        async fn prepare_cycle(&self) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn should_process(&self, _trigger: &Trigger) -> bool {
+            !self.skip
+        }
+
+        async fn execute(&self, _trigger: Trigger) -> anyhow::Result<()> {
+            self.executed.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn coordinator(strategy: NoopStrategy) -> (Coordinator<NoopStrategy>, watch::Sender<bool>) {
+        let (paused_tx, paused_rx) = watch::channel(false);
+        let coordinator = Coordinator::new(strategy, GracefulShutdown::default(), paused_rx);
+
+        (coordinator, paused_tx)
+    }
+
+    #[tokio::test]
+    async fn test_cycle_executes() {
+        let (mut coordinator, _paused_tx) = coordinator(NoopStrategy::default());
+
+        coordinator.cycle().await.unwrap();
+        coordinator.cycle().await.unwrap();
+
+        assert_eq!(coordinator.strategy.executed.load(Ordering::SeqCst), 2);
+        assert_eq!(coordinator.cycle, 2);
+    }
+
+    #[tokio::test]
+    async fn test_cycle_skips() {
+        let (mut coordinator, _paused_tx) = coordinator(NoopStrategy {
+            skip: true,
+            ..Default::default()
+        });
+
+        coordinator.cycle().await.unwrap();
+
+        assert_eq!(coordinator.strategy.executed.load(Ordering::SeqCst), 0);
+        assert_eq!(coordinator.cycle, 1);
+    }
+
+    #[tokio::test]
+    async fn test_cycle_shutdown_requested() {
+        let (mut coordinator, _paused_tx) = coordinator(NoopStrategy::default());
+        let shutdown = coordinator.shutdown.clone();
+        shutdown.request();
+
+        // a stopped coordinator parks its loop instead of returning
+        tokio::select! {
+            _ = coordinator.cycle() => panic!("cycle returned after shutdown"),
+            _ = shutdown.stopped() => {}
+        }
+
+        assert_eq!(coordinator.strategy.executed.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cycle_shutdown_while_paused() {
+        let (mut coordinator, paused_tx) = coordinator(NoopStrategy::default());
+        let shutdown = coordinator.shutdown.clone();
+        paused_tx.send_replace(true);
+
+        tokio::select! {
+            _ = coordinator.cycle() => panic!("cycle returned while paused"),
+            _ = async {
+                tokio::task::yield_now().await;
+                shutdown.request();
+                shutdown.stopped().await;
+            } => {}
+        }
+
+        assert_eq!(coordinator.strategy.executed.load(Ordering::SeqCst), 0);
+    }
+}
@@ -1,15 +1,21 @@
+pub mod control;
 pub mod engine;
 pub mod strategy;
 
-use std::fs;
+use std::env;
 
-use common::{artifacts_dir, NeutronStrategyConfig};
+use common::{artifacts_dir, EnvOverrides, NeutronStrategyConfig};
+use control::CoordinatorControl;
 use dotenv::dotenv;
+use engine::{Coordinator, GracefulShutdown};
 use log::{info, warn};
 use strategy::Strategy;
+use tokio::sync::oneshot;
 use valence_coordinator_sdk::coordinator::ValenceCoordinator;
 
 const RUNNER: &str = "runner";
+/// admin api address, kept off 9090 which is taken by the cosmos grpc server
+const DEFAULT_ADMIN_ADDR: &str = "127.0.0.1:8090";
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -25,22 +31,51 @@ async fn main() -> anyhow::Result<()> {
 
     info!(target: RUNNER, "Using ntrn config: {}", neutron_cfg_path.display());
 
-    let parameters = fs::read_to_string(neutron_cfg_path)?;
+    // values provisioned into the toml config can be overridden with
+    // `NEUTRON_`-prefixed env variables
+    let neutron_cfg = NeutronStrategyConfig::merge(
+        NeutronStrategyConfig::from_toml(neutron_cfg_path)?,
+        EnvOverrides::from_env(),
+    );
 
-    let neutron_cfg: NeutronStrategyConfig = toml::from_str(&parameters)?;
+    let control = CoordinatorControl::default();
 
     let strategy = Strategy::new(neutron_cfg).await?;
 
     info!(target: RUNNER, "strategy initialized");
+
+    // the admin api (pause/resume) is only exposed if a token is configured
+    match env::var("ADMIN_TOKEN") {
+        Ok(admin_token) => {
+            let admin_addr =
+                env::var("ADMIN_ADDR").unwrap_or_else(|_| DEFAULT_ADMIN_ADDR.to_string());
+            control::spawn_admin_api(&admin_addr, control.clone(), admin_token).await?;
+        }
+        Err(_) => info!(target: RUNNER, "ADMIN_TOKEN not set, admin api disabled"),
+    }
+
+    let shutdown = GracefulShutdown::default();
+    shutdown.listen()?;
+
     info!(target: RUNNER, "starting the coordinator");
 
-    let coordinator_join_handle = strategy.start();
+    let coordinator_join_handle =
+        Coordinator::new(strategy, shutdown.clone(), control.subscribe()).start();
+
+    // joining blocks, so it happens on a dedicated thread that reports back
+    // once the coordinator thread finishes
+    let (coordinator_done_tx, coordinator_done_rx) = oneshot::channel();
+    std::thread::spawn(move || coordinator_done_tx.send(coordinator_join_handle.join()));
 
-    // join here will wait for the coordinator thread to finish which should never happen
-    // in practice since it runs an infinite stayalive loop
-    match coordinator_join_handle.join() {
-        Ok(t) => warn!(target: RUNNER, "coordinator thread completed: {t:?}"),
-        Err(e) => warn!(target: RUNNER, "coordinator thread completed with error: {e:?}"),
+    // the coordinator thread runs an infinite stayalive loop, so in practice
+    // the runner only exits once a shutdown signal was received and the
+    // in-flight cycle (if any) has completed
+    tokio::select! {
+        _ = shutdown.stopped() => info!(target: RUNNER, "coordinator stopped, exiting"),
+        res = coordinator_done_rx => match res? {
+            Ok(t) => warn!(target: RUNNER, "coordinator thread completed: {t:?}"),
+            Err(e) => warn!(target: RUNNER, "coordinator thread completed with error: {e:?}"),
+        },
     }
 
     Ok(())
@@ -1,8 +1,36 @@
 use std::env;
 
+use async_trait::async_trait;
 use common::NeutronStrategyConfig;
 use valence_domain_clients::clients::{coprocessor::CoprocessorClient, neutron::NeutronClient};
 
+/// event handed to a strategy on every coordinator cycle
+#[derive(Debug, Clone)]
+pub struct Trigger {
+    /// number of cycles started before this one
+    pub cycle: u64,
+}
+
+/// chain specific logic driven by the coordinator. the coordinator calls
+/// `prepare_cycle` at the start of every cycle and then `execute`s the
+/// cycle's trigger if the strategy opts in via `should_process`.
+#[async_trait]
+pub trait CoordinatorStrategy: Send + Sync {
+    /// strategy name
+    fn name(&self) -> String;
+
+    /// waits until the strategy is ready to run the next cycle
+    async fn prepare_cycle(&self) -> anyhow::Result<()>;
+
+    /// whether the given trigger should be executed or skipped
+    fn should_process(&self, trigger: &Trigger) -> bool;
+
+    /// runs the strategy for the given trigger
+    async fn execute(&self, trigger: Trigger) -> anyhow::Result<()>;
+}
+
+/// neutron strategy: proves an erc20 balance on the co-processor and
+/// posts the proof to the neutron authorizations contract
 pub(crate) struct Strategy {
     /// strategy name
     pub label: String,
@@ -1,12 +1,21 @@
-use std::fs;
+use std::{
+    fs,
+    io::Write,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
 
 use anyhow::anyhow;
 use common::artifacts_dir;
 use log::info;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::PROVISIONER;
 
+/// version of the artifact file layout. bump this whenever the shape of
+/// the persisted outputs changes so that stale files fail loudly on read.
+pub(crate) const ARTIFACTS_SCHEMA_VERSION: u64 = 1;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct InstantiationOutputs {
     pub authorizations: String,
@@ -19,36 +28,226 @@ pub struct CoprocessorOutputs {
     pub coprocessor_app_id: String,
 }
 
-pub(crate) fn write_instantiation_artifacts(outputs: InstantiationOutputs) -> anyhow::Result<()> {
-    let path = artifacts_dir().join("instantiation_outputs.toml");
-    info!(target: PROVISIONER, "writing on-chain instantiation artifacts to {}", path.display());
-    fs::write(path, toml::to_string(&outputs)?)?;
+#[derive(Serialize, Deserialize)]
+struct ArtifactEnvelope<T> {
+    schema_version: u64,
+    data: T,
+}
+
+/// permissions of the written artifacts. set explicitly as temporary
+/// files are created owner-only (0600) and would keep that mode.
+#[cfg(unix)]
+const ARTIFACT_FILE_MODE: u32 = 0o644;
+
+/// writes `content` to `path` through a temporary file in the same
+/// directory that is renamed into place, so that a crash mid-write never
+/// leaves a partially written file behind.
+pub(crate) fn write_atomic(path: &Path, content: &[u8]) -> anyhow::Result<()> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| anyhow!("invalid artifact path {}", path.display()))?;
+
+    let mut tmp_file = tempfile::NamedTempFile::new_in(dir)?;
+    tmp_file.write_all(content)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        tmp_file
+            .as_file()
+            .set_permissions(fs::Permissions::from_mode(ARTIFACT_FILE_MODE))?;
+    }
+
+    tmp_file.as_file().sync_all()?;
+    tmp_file.persist(path)?;
+
     Ok(())
 }
 
+/// versioned, toml-encoded artifact file. writes are atomic (see `write_atomic`).
+pub(crate) struct ArtifactStore<T> {
+    path: PathBuf,
+    /// hint returned when the artifact has not been produced yet
+    missing_hint: &'static str,
+    _data: PhantomData<T>,
+}
+
+impl<T: Serialize + DeserializeOwned> ArtifactStore<T> {
+    pub(crate) fn new(dir: &Path, file_name: &str, missing_hint: &'static str) -> Self {
+        Self {
+            path: dir.join(file_name),
+            missing_hint,
+            _data: PhantomData,
+        }
+    }
+
+    pub(crate) fn write(&self, data: T) -> anyhow::Result<()> {
+        let envelope = ArtifactEnvelope {
+            schema_version: ARTIFACTS_SCHEMA_VERSION,
+            data,
+        };
+        let content = toml::to_string(&envelope)?;
+
+        write_atomic(&self.path, content.as_bytes())
+    }
+
+    pub(crate) fn read(&self) -> anyhow::Result<T> {
+        let content = fs::read_to_string(&self.path).map_err(|_| anyhow!(self.missing_hint))?;
+
+        let mut table: toml::Table = toml::from_str(&content)
+            .map_err(|e| anyhow!("failed to parse {}: {e}", self.path.display()))?;
+
+        // artifacts written by an older (or newer) provisioner need to be
+        // regenerated by re-running the step that produces them
+        match table
+            .get("schema_version")
+            .and_then(toml::Value::as_integer)
+        {
+            Some(v) if v == ARTIFACTS_SCHEMA_VERSION as i64 => {}
+            Some(v) => anyhow::bail!(
+                "{} has schema version {v}, expected {ARTIFACTS_SCHEMA_VERSION}",
+                self.path.display()
+            ),
+            None => anyhow::bail!("{} has no schema version", self.path.display()),
+        }
+
+        let data = table
+            .remove("data")
+            .ok_or_else(|| anyhow!("{} has no data section", self.path.display()))?;
+
+        data.try_into()
+            .map_err(|e| anyhow!("failed to reconstruct {}: {e}", self.path.display()))
+    }
+}
+
+fn instantiation_store() -> ArtifactStore<InstantiationOutputs> {
+    ArtifactStore::new(
+        &artifacts_dir(),
+        "instantiation_outputs.toml",
+        "on-chain instantiation artifacts not found. run --instantiate-contracts step first.",
+    )
+}
+
+fn coprocessor_store() -> ArtifactStore<CoprocessorOutputs> {
+    ArtifactStore::new(
+        &artifacts_dir(),
+        "coprocessor_outputs.toml",
+        "co-processor artifacts not found. run --deploy-coprocessor step first.",
+    )
+}
+
+pub(crate) fn write_instantiation_artifacts(outputs: InstantiationOutputs) -> anyhow::Result<()> {
+    let store = instantiation_store();
+    info!(target: PROVISIONER, "writing on-chain instantiation artifacts to {}", store.path.display());
+    store.write(outputs)
+}
+
 pub(crate) fn read_instantiation_artifacts() -> anyhow::Result<InstantiationOutputs> {
-    let path = artifacts_dir().join("instantiation_outputs.toml");
-    let content = fs::read_to_string(path).map_err(|_| {
-        anyhow!(
-            "on-chain instantiation artifacts not found. run --instantiate-contracts step first."
-        )
-    })?;
-    toml::from_str(&content)
-        .map_err(|e| anyhow::anyhow!("failed to reconstruct instantiation outputs: {e}"))
+    instantiation_store().read()
 }
 
 pub(crate) fn write_coprocessor_artifacts(outputs: CoprocessorOutputs) -> anyhow::Result<()> {
-    let path = artifacts_dir().join("coprocessor_outputs.toml");
-    info!(target: PROVISIONER, "writing co-processor deployment artifacts to {}", path.display());
-    fs::write(path, toml::to_string(&outputs)?)?;
-    Ok(())
+    let store = coprocessor_store();
+    info!(target: PROVISIONER, "writing co-processor deployment artifacts to {}", store.path.display());
+    store.write(outputs)
 }
 
 pub(crate) fn read_coprocessor_artifacts() -> anyhow::Result<CoprocessorOutputs> {
-    let path = artifacts_dir().join("coprocessor_outputs.toml");
-    let content = fs::read_to_string(path).map_err(|_| {
-        anyhow!("co-processor artifacts not found. run --deploy-coprocessor step first.")
-    })?;
-    toml::from_str(&content)
-        .map_err(|e| anyhow::anyhow!("failed to reconstruct coprocessor step outputs: {e}"))
+    coprocessor_store().read()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE_NAME: &str = "outputs.toml";
+
+    fn store(dir: &Path) -> ArtifactStore<CoprocessorOutputs> {
+        ArtifactStore::new(dir, FILE_NAME, "missing")
+    }
+
+    #[test]
+    fn test_artifact_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = store(dir.path());
+
+        store
+            .write(CoprocessorOutputs {
+                coprocessor_app_id: "app".to_string(),
+            })
+            .unwrap();
+
+        assert_eq!(store.read().unwrap().coprocessor_app_id, "app");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_artifact_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = store(dir.path());
+        store
+            .write(CoprocessorOutputs {
+                coprocessor_app_id: "app".to_string(),
+            })
+            .unwrap();
+
+        let mode = fs::metadata(dir.path().join(FILE_NAME))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, ARTIFACT_FILE_MODE);
+    }
+
+    #[test]
+    fn test_artifact_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let err = store(dir.path()).read().unwrap_err();
+
+        assert_eq!(err.to_string(), "missing");
+    }
+
+    #[test]
+    fn test_artifact_wrong_schema_version() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(FILE_NAME),
+            "schema_version = 2\n\n[data]\ncoprocessor_app_id = \"app\"\n",
+        )
+        .unwrap();
+
+        let err = store(dir.path()).read().unwrap_err();
+
+        assert!(err.to_string().contains("has schema version 2"));
+    }
+
+    #[test]
+    fn test_artifact_missing_schema_version() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(FILE_NAME),
+            "[data]\ncoprocessor_app_id = \"app\"\n",
+        )
+        .unwrap();
+
+        let err = store(dir.path()).read().unwrap_err();
+
+        assert!(err.to_string().contains("has no schema version"));
+    }
+
+    #[test]
+    fn test_artifact_missing_data() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(FILE_NAME),
+            format!("schema_version = {ARTIFACTS_SCHEMA_VERSION}\n"),
+        )
+        .unwrap();
+
+        let err = store(dir.path()).read().unwrap_err();
+
+        assert!(err.to_string().contains("has no data section"));
+    }
 }
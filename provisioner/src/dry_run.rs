@@ -0,0 +1,184 @@
+use async_trait::async_trait;
+use cosmrs::{proto::cosmos::base::abci::v1beta1::TxResponse, tx::Fee, Coin};
+use log::info;
+use serde::Serialize;
+use valence_domain_clients::{
+    clients::neutron::NeutronClient,
+    common::transaction::TransactionResponse,
+    cosmos::{
+        base_client::BaseClient, grpc_client::GrpcSigningClient, signing_client::SigningClient,
+        wasm_client::WasmClient,
+    },
+};
+
+const DRY_RUN: &str = "DRY_RUN";
+
+/// placeholder hash of the transactions that are not broadcast
+const DRY_RUN_TX_HASH: &str = "<dry run: not broadcast>";
+
+/// neutron client that performs all queries (code info, instantiate2
+/// address prediction) against the configured node but only logs the
+/// transactions it is asked to broadcast, returning synthetic responses
+/// instead. it never signs: `get_signing_client` fails, so the default
+/// trait methods that are not overridden here cannot broadcast either.
+pub(crate) struct DryRunNeutronClient {
+    inner: NeutronClient,
+    /// address the transactions would be signed by
+    signer_address: String,
+}
+
+impl DryRunNeutronClient {
+    pub(crate) fn new(inner: NeutronClient, signer_address: String) -> Self {
+        Self {
+            inner,
+            signer_address,
+        }
+    }
+}
+
+fn synthetic_tx_response() -> TransactionResponse {
+    TransactionResponse {
+        hash: DRY_RUN_TX_HASH.to_string(),
+        success: true,
+        block_height: 0,
+        gas_used: 0,
+    }
+}
+
+fn to_json(msg: &impl Serialize) -> String {
+    serde_json::to_string_pretty(msg).unwrap_or_else(|e| format!("<unserializable msg: {e}>"))
+}
+
+#[async_trait]
+impl GrpcSigningClient for DryRunNeutronClient {
+    fn grpc_url(&self) -> String {
+        self.inner.grpc_url()
+    }
+
+    fn mnemonic(&self) -> String {
+        self.inner.mnemonic()
+    }
+
+    fn chain_prefix(&self) -> String {
+        self.inner.chain_prefix()
+    }
+
+    fn chain_id(&self) -> String {
+        self.inner.chain_id()
+    }
+
+    fn chain_denom(&self) -> String {
+        self.inner.chain_denom()
+    }
+
+    fn gas_price(&self) -> f64 {
+        self.inner.gas_price()
+    }
+
+    fn gas_adjustment(&self) -> f64 {
+        self.inner.gas_adjustment()
+    }
+
+    async fn get_signing_client(&self) -> anyhow::Result<SigningClient> {
+        anyhow::bail!("dry run: transactions cannot be signed")
+    }
+}
+
+#[async_trait]
+impl BaseClient for DryRunNeutronClient {
+    async fn transfer(
+        &self,
+        to: &str,
+        amount: u128,
+        denom: &str,
+        _memo: Option<&str>,
+    ) -> anyhow::Result<TransactionResponse> {
+        info!(target: DRY_RUN, "would transfer {amount}{denom} to {to}");
+        Ok(synthetic_tx_response())
+    }
+
+    async fn ibc_transfer(
+        &self,
+        to: String,
+        denom: String,
+        amount: String,
+        channel_id: String,
+        _timeout_seconds: u64,
+        _memo: Option<String>,
+    ) -> anyhow::Result<TransactionResponse> {
+        info!(target: DRY_RUN, "would ibc transfer {amount}{denom} to {to} over {channel_id}");
+        Ok(synthetic_tx_response())
+    }
+
+    async fn poll_for_tx(&self, tx_hash: &str) -> anyhow::Result<TxResponse> {
+        Ok(TxResponse {
+            txhash: tx_hash.to_string(),
+            code: 0,
+            ..Default::default()
+        })
+    }
+}
+
+#[async_trait]
+impl WasmClient for DryRunNeutronClient {
+    async fn upload_code(&self, wasm_path: &str) -> anyhow::Result<u64> {
+        info!(target: DRY_RUN, "would upload wasm code from {wasm_path}");
+        Ok(0)
+    }
+
+    async fn instantiate(
+        &self,
+        code_id: u64,
+        label: String,
+        msg: impl Serialize + Send,
+        admin: Option<String>,
+    ) -> anyhow::Result<String> {
+        info!(
+            target: DRY_RUN,
+            "would instantiate code id {code_id} with label {label} (admin: {admin:?}): {}",
+            to_json(&msg)
+        );
+
+        // plain instantiation addresses depend on the chain's instance
+        // counter, so they cannot be predicted ahead of time
+        Ok(format!("<{label} address>"))
+    }
+
+    async fn instantiate2(
+        &self,
+        code_id: u64,
+        label: String,
+        msg: impl Serialize + Send,
+        admin: Option<String>,
+        salt: String,
+    ) -> anyhow::Result<String> {
+        let predicted_addr = self
+            .predict_instantiate2_addr(code_id, salt.clone(), self.signer_address.clone())
+            .await?
+            .address;
+
+        info!(
+            target: DRY_RUN,
+            "would instantiate2 code id {code_id} with label {label} and salt {salt} at {predicted_addr} (admin: {admin:?}): {}",
+            to_json(&msg)
+        );
+
+        Ok(predicted_addr)
+    }
+
+    async fn execute_wasm(
+        &self,
+        contract: &str,
+        msg: impl Serialize + Send,
+        funds: Vec<Coin>,
+        _fees: Option<Fee>,
+    ) -> anyhow::Result<TransactionResponse> {
+        info!(
+            target: DRY_RUN,
+            "would execute on {contract} with funds {funds:?}: {}",
+            to_json(&msg)
+        );
+
+        Ok(synthetic_tx_response())
+    }
+}
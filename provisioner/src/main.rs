@@ -1,13 +1,22 @@
 mod artifacts;
+mod dry_run;
 mod steps;
 
 use std::env;
 
 use clap::Parser;
 use common::NeutronStrategyConfig;
-use valence_domain_clients::clients::{coprocessor::CoprocessorClient, neutron::NeutronClient};
-
-use crate::artifacts::CoprocessorOutputs;
+use log::{info, warn};
+use valence_domain_clients::{
+    clients::{coprocessor::CoprocessorClient, neutron::NeutronClient},
+    cosmos::{grpc_client::GrpcSigningClient, wasm_client::WasmClient},
+};
+
+use crate::{
+    artifacts::{CoprocessorOutputs, InstantiationOutputs},
+    dry_run::DryRunNeutronClient,
+    steps::NeutronInputs,
+};
 
 use clap::ValueEnum;
 
@@ -19,6 +28,11 @@ struct Cli {
     /// which step to run. Defaults to `all`.
     #[arg(long, value_enum, default_value_t = Step::All)]
     step: Step,
+    /// log the messages that would be broadcast without signing or
+    /// submitting any transactions. no artifacts are written and the
+    /// co-processor app is neither built nor deployed.
+    #[arg(long)]
+    dry_run: bool,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
@@ -59,12 +73,70 @@ async fn main() -> anyhow::Result<()> {
     )
     .await?;
 
-    // first step is to instantiate the on-chain contracts
-    match cli.step {
+    // resolved once up front: the dry run client refuses to hand out its
+    // signing client so that nothing can be broadcast through it
+    let signer_address = neutron_client
+        .get_signing_client()
+        .await?
+        .address
+        .to_string();
+
+    if cli.dry_run {
+        info!(target: PROVISIONER, "dry run: no transactions will be broadcast and no artifacts will be written");
+        let dry_run_client = DryRunNeutronClient::new(neutron_client, signer_address.clone());
+        run_steps(
+            cli.step,
+            &dry_run_client,
+            &signer_address,
+            &cp_client,
+            &neutron_inputs,
+            true,
+        )
+        .await
+    } else {
+        run_steps(
+            cli.step,
+            &neutron_client,
+            &signer_address,
+            &cp_client,
+            &neutron_inputs,
+            false,
+        )
+        .await
+    }
+}
+
+async fn run_steps(
+    step: Step,
+    neutron_client: &impl WasmClient,
+    signer_address: &str,
+    cp_client: &CoprocessorClient,
+    neutron_inputs: &NeutronInputs,
+    dry_run: bool,
+) -> anyhow::Result<()> {
+    // outputs produced during this run. in dry-run mode they are only
+    // kept in memory and handed to the subsequent steps.
+    let mut instantiation_outputs: Option<InstantiationOutputs> = None;
+    let mut coprocessor_outputs: Option<CoprocessorOutputs> = None;
+
+    // first step is to instantiate the on-chain contracts. the configured
+    // code ids are validated before any address is predicted
+    match step {
         Step::All | Step::InstantiateContracts => {
-            let instantiation_outputs =
-                steps::instantiate_contracts(&neutron_client, neutron_inputs.code_ids).await?;
-            artifacts::write_instantiation_artifacts(instantiation_outputs)?;
+            steps::validate_code_ids(neutron_client, &neutron_inputs.code_ids).await?;
+
+            let outputs = steps::instantiate_contracts(
+                neutron_client,
+                signer_address,
+                neutron_inputs.code_ids.clone(),
+            )
+            .await?;
+            if dry_run {
+                info!(target: PROVISIONER, "would write instantiation artifacts: {outputs:?}");
+            } else {
+                artifacts::write_instantiation_artifacts(outputs.clone())?;
+            }
+            instantiation_outputs = Some(outputs);
         }
         _ => {}
     };
@@ -73,13 +145,25 @@ async fn main() -> anyhow::Result<()> {
     // this depends on the first step and can be seen as the second step
     // in a tcp handshake as the cw20 address from step 1 is embedded
     // into our circuits before they get compiled
-    // if run_all || cli.deploy_coprocessor {
-    match cli.step {
+    match step {
         Step::All | Step::DeployCoprocessor => {
-            let instantiation_outputs = artifacts::read_instantiation_artifacts()?;
-            let coprocessor_app_id =
-                steps::deploy_coprocessor_app(&cp_client, &instantiation_outputs.cw20).await?;
-            artifacts::write_coprocessor_artifacts(CoprocessorOutputs { coprocessor_app_id })?;
+            let instantiation_outputs = match &instantiation_outputs {
+                Some(outputs) => outputs.clone(),
+                None => artifacts::read_instantiation_artifacts()?,
+            };
+            if dry_run {
+                info!(
+                    target: PROVISIONER,
+                    "would build and deploy the co-processor app for cw20 {}",
+                    instantiation_outputs.cw20
+                );
+            } else {
+                let coprocessor_app_id =
+                    steps::deploy_coprocessor_app(cp_client, &instantiation_outputs.cw20).await?;
+                let outputs = CoprocessorOutputs { coprocessor_app_id };
+                artifacts::write_coprocessor_artifacts(outputs.clone())?;
+                coprocessor_outputs = Some(outputs);
+            }
         }
         _ => {}
     };
@@ -87,10 +171,38 @@ async fn main() -> anyhow::Result<()> {
     // finally, we set up the on-chain authorizations. this can be seen
     // as the final step in a tcp handshake where our on-chain contracts
     // are made aware of the coprocessor deployment.
-    match cli.step {
+    match step {
         Step::All | Step::Authorize => {
-            let instantiation_outputs = artifacts::read_instantiation_artifacts()?;
-            let coprocessor_outputs = artifacts::read_coprocessor_artifacts()?;
+            // the authorization embeds the verifying key of an already
+            // deployed co-processor app, which a dry run never deploys. the
+            // contracts previewed in this run (with placeholder addresses)
+            // are unrelated to any previous deployment, so the preview only
+            // runs against existing artifacts, i.e. with `--step authorize`.
+            if dry_run && instantiation_outputs.is_some() {
+                warn!(
+                    target: PROVISIONER,
+                    "skipping authorization setup: it would combine the contracts previewed in this run \
+                     with a previous co-processor deployment. run with `--step authorize` to preview it \
+                     against the existing artifacts"
+                );
+                return Ok(());
+            }
+
+            let instantiation_outputs = match instantiation_outputs {
+                Some(outputs) => outputs,
+                None => artifacts::read_instantiation_artifacts()?,
+            };
+            let coprocessor_outputs = match coprocessor_outputs {
+                Some(outputs) => outputs,
+                None if dry_run => match artifacts::read_coprocessor_artifacts() {
+                    Ok(outputs) => outputs,
+                    Err(e) => {
+                        warn!(target: PROVISIONER, "skipping authorization setup: {e}");
+                        return Ok(());
+                    }
+                },
+                None => artifacts::read_coprocessor_artifacts()?,
+            };
             let neutron_strategy_config = NeutronStrategyConfig {
                 grpc_url: neutron_inputs.grpc_url.clone(),
                 grpc_port: neutron_inputs.grpc_port.clone(),
@@ -100,10 +212,19 @@ async fn main() -> anyhow::Result<()> {
                 cw20: instantiation_outputs.cw20,
                 coprocessor_app_id: coprocessor_outputs.coprocessor_app_id,
             };
-            steps::setup_authorizations(&neutron_client, &cp_client, &neutron_strategy_config)
-                .await?;
-
-            steps::write_setup_artifacts(neutron_strategy_config)?;
+            steps::setup_authorizations(
+                neutron_client,
+                signer_address,
+                cp_client,
+                &neutron_strategy_config,
+            )
+            .await?;
+
+            if dry_run {
+                info!(target: PROVISIONER, "would write strategy config: {neutron_strategy_config:?}");
+            } else {
+                steps::write_setup_artifacts(neutron_strategy_config)?;
+            }
         }
         _ => {}
     };
@@ -1,6 +1,6 @@
 use std::fs;
 
-use common::{artifacts_dir, zk_apps_dir};
+use common::{artifacts_dir, zk_apps_dir, ZK_MINT_CW20_REGISTRY};
 use log::info;
 use valence_domain_clients::{
     clients::coprocessor::CoprocessorClient, coprocessor::base_client::CoprocessorBaseClient,
@@ -23,11 +23,11 @@ pub async fn deploy_coprocessor_app(
         .join("src")
         .join("consts.rs");
     let generated_addr_content = format!(
-        "// DO NOT EDIT. This file is automatically generated by deploy_coprocessor_app.rs.\npub const CW20_ADDR: &str = \"{cw20_addr}\";",
+        "// DO NOT EDIT. This file is automatically generated by deploy_coprocessor_app.rs.\npub const CW20_ADDR: &str = \"{cw20_addr}\";\npub const ZK_MINT_CW20_REGISTRY: u64 = {ZK_MINT_CW20_REGISTRY};",
     );
 
     fs::write(&generated_addr_path, generated_addr_content)?;
-    info!(target: CO_PROCESSOR, "embedded CW20 address into {generated_addr_path:?}");
+    info!(target: CO_PROCESSOR, "embedded CW20 address and registry id into {generated_addr_path:?}");
 
     // build the artifacts. this puts the resulting binaries under
     // `valence.artifacts` value specified in `valence.toml`.
@@ -2,10 +2,7 @@ use std::time::SystemTime;
 
 use cw20::MinterResponse;
 use log::info;
-use valence_domain_clients::{
-    clients::neutron::NeutronClient,
-    cosmos::{base_client::BaseClient, grpc_client::GrpcSigningClient, wasm_client::WasmClient},
-};
+use valence_domain_clients::cosmos::{base_client::BaseClient, wasm_client::WasmClient};
 
 use crate::{artifacts::InstantiationOutputs, steps::read_input::CodeIds};
 
@@ -14,17 +11,11 @@ const VALENCE_NEUTRON_VERIFICATION_ROUTER: &str =
 const CONTRACT_DEPLOYMENT: &str = "CONTRACT_DEPLOYMENT";
 
 pub async fn instantiate_contracts(
-    neutron_client: &NeutronClient,
+    neutron_client: &impl WasmClient,
+    my_address: &str,
     code_ids: CodeIds,
 ) -> anyhow::Result<InstantiationOutputs> {
     info!(target: CONTRACT_DEPLOYMENT, "instantiating contracts...");
-
-    let my_address = neutron_client
-        .get_signing_client()
-        .await?
-        .address
-        .to_string();
-
     info!(target: CONTRACT_DEPLOYMENT, "runner address: {my_address}");
 
     let now = SystemTime::now();
@@ -35,7 +26,7 @@ pub async fn instantiate_contracts(
     let salt = hex::encode(salt_raw.as_bytes());
 
     let predicted_processor_address = neutron_client
-        .predict_instantiate2_addr(code_ids.processor, salt.clone(), my_address.clone())
+        .predict_instantiate2_addr(code_ids.processor, salt.clone(), my_address.to_string())
         .await?
         .address;
 
@@ -117,7 +108,7 @@ pub async fn instantiate_contracts(
             code_ids.cw20,
             "mirror_cw20".to_string(),
             cw20_init_msg,
-            Some(my_address),
+            Some(my_address.to_string()),
         )
         .await?;
 
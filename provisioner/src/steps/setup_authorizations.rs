@@ -1,4 +1,4 @@
-use common::{NeutronStrategyConfig, ZK_MINT_CW20_LABEL};
+use common::{NeutronStrategyConfig, ZK_MINT_CW20_LABEL, ZK_MINT_CW20_REGISTRY};
 use cosmwasm_std::Binary;
 use log::info;
 use sp1_sdk::{HashableKey, SP1VerifyingKey};
@@ -7,26 +7,21 @@ use valence_authorization_utils::{
     zk_authorization::ZkAuthorizationInfo,
 };
 use valence_domain_clients::{
-    clients::{coprocessor::CoprocessorClient, neutron::NeutronClient},
+    clients::coprocessor::CoprocessorClient,
     coprocessor::base_client::CoprocessorBaseClient,
-    cosmos::{base_client::BaseClient, grpc_client::GrpcSigningClient, wasm_client::WasmClient},
+    cosmos::{base_client::BaseClient, wasm_client::WasmClient},
 };
 
 const VERIFICATION_ROUTE: &str = "0001/sp1/5.0.8/groth16";
 const AUTH_SETUP: &str = "AUTH_SETUP";
 
 pub async fn setup_authorizations(
-    neutron_client: &NeutronClient,
+    neutron_client: &impl WasmClient,
+    my_address: &str,
     cp_client: &CoprocessorClient,
     ntrn_strategy_config: &NeutronStrategyConfig,
 ) -> anyhow::Result<()> {
     info!(target: AUTH_SETUP, "setting up authorizations...");
-    let my_address = neutron_client
-        .get_signing_client()
-        .await?
-        .address
-        .to_string();
-
     info!(target: AUTH_SETUP, "my address: {my_address}");
 
     let authorization_permissioned_mode =
@@ -47,7 +42,7 @@ pub async fn setup_authorizations(
 }
 
 async fn create_zk_cw20_mint_authorization(
-    neutron_client: &NeutronClient,
+    neutron_client: &impl WasmClient,
     cp_client: &CoprocessorClient,
     cfg: &NeutronStrategyConfig,
     authorization_mode: AuthorizationModeInfo,
@@ -59,7 +54,7 @@ async fn create_zk_cw20_mint_authorization(
     let zk_authorization = ZkAuthorizationInfo {
         label: ZK_MINT_CW20_LABEL.to_string(),
         mode: authorization_mode,
-        registry: 0,
+        registry: ZK_MINT_CW20_REGISTRY,
         vk: Binary::from(sp1_program_vk.bytes32().as_bytes()),
         validate_last_block_execution: false,
         verification_route: VERIFICATION_ROUTE.to_string(),
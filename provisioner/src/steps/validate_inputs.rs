@@ -0,0 +1,186 @@
+use std::fmt;
+
+use log::info;
+use valence_domain_clients::cosmos::wasm_client::WasmClient;
+
+use crate::steps::read_input::CodeIds;
+
+const VALIDATE_INPUTS: &str = "VALIDATE_INPUTS";
+
+/// returned when a configured code id is not stored on the target chain
+#[derive(Debug)]
+pub struct CodeIdNotFound(pub u64);
+
+impl fmt::Display for CodeIdNotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "code id {} not found on chain", self.0)
+    }
+}
+
+impl std::error::Error for CodeIdNotFound {}
+
+/// checks that every configured code id exists on chain so that a bad
+/// input fails before any address is predicted or transaction is sent
+pub async fn validate_code_ids(
+    neutron_client: &impl WasmClient,
+    code_ids: &CodeIds,
+) -> anyhow::Result<()> {
+    info!(target: VALIDATE_INPUTS, "validating code ids...");
+
+    for code_id in [code_ids.authorizations, code_ids.processor, code_ids.cw20] {
+        match neutron_client.query_code_info(code_id).await {
+            Ok(resp) if resp.code_info.is_some() => {}
+            Ok(_) => return Err(CodeIdNotFound(code_id).into()),
+            Err(e) => return Err(code_info_query_error(code_id, e)),
+        }
+    }
+
+    info!(target: VALIDATE_INPUTS, "code ids validated");
+
+    Ok(())
+}
+
+/// wasmd responds to unknown (or invalid) code ids with an error status
+/// rather than an empty response, so those are told apart from other
+/// query failures by their grpc status code
+fn code_info_query_error(code_id: u64, e: anyhow::Error) -> anyhow::Error {
+    match e.downcast_ref::<tonic::Status>().map(tonic::Status::code) {
+        Some(tonic::Code::NotFound | tonic::Code::InvalidArgument) => {
+            CodeIdNotFound(code_id).into()
+        }
+        _ => e.context(format!("failed to query code id {code_id}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use cosmrs::proto::cosmwasm::wasm::v1::{CodeInfoResponse, QueryCodeResponse};
+    use valence_domain_clients::cosmos::{base_client::BaseClient, grpc_client::GrpcSigningClient};
+
+    use super::*;
+
+    const MISSING_CODE_ID: u64 = 2;
+
+    /// wasm client answering code info queries without a node
+    struct MockWasmClient {
+        missing_code: fn(u64) -> anyhow::Result<QueryCodeResponse>,
+    }
+
+    impl GrpcSigningClient for MockWasmClient {
+        fn grpc_url(&self) -> String {
+            String::new()
+        }
+
+        fn mnemonic(&self) -> String {
+            String::new()
+        }
+
+        fn chain_prefix(&self) -> String {
+            "neutron".to_string()
+        }
+
+        fn chain_id(&self) -> String {
+            String::new()
+        }
+
+        fn chain_denom(&self) -> String {
+            "untrn".to_string()
+        }
+
+        fn gas_price(&self) -> f64 {
+            0.0
+        }
+
+        fn gas_adjustment(&self) -> f64 {
+            0.0
+        }
+    }
+
+    impl BaseClient for MockWasmClient {}
+
+    #[async_trait]
+    impl WasmClient for MockWasmClient {
+        async fn query_code_info(&self, code_id: u64) -> anyhow::Result<QueryCodeResponse> {
+            if code_id == MISSING_CODE_ID {
+                return (self.missing_code)(code_id);
+            }
+
+            Ok(QueryCodeResponse {
+                code_info: Some(CodeInfoResponse {
+                    code_id,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+        }
+    }
+
+    fn code_ids() -> CodeIds {
+        CodeIds {
+            authorizations: 1,
+            processor: MISSING_CODE_ID,
+            cw20: 3,
+        }
+    }
+
+    async fn validate(
+        missing_code: fn(u64) -> anyhow::Result<QueryCodeResponse>,
+    ) -> anyhow::Result<()> {
+        validate_code_ids(&MockWasmClient { missing_code }, &code_ids()).await
+    }
+
+    fn assert_code_id_not_found(res: anyhow::Result<()>) {
+        let err = res.unwrap_err();
+        let not_found = err.downcast_ref::<CodeIdNotFound>().unwrap();
+
+        assert_eq!(not_found.0, MISSING_CODE_ID);
+    }
+
+    #[tokio::test]
+    async fn test_validate_code_ids() {
+        let mut code_ids = code_ids();
+        code_ids.processor = 4;
+
+        validate_code_ids(
+            &MockWasmClient {
+                missing_code: |_| unreachable!(),
+            },
+            &code_ids,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_validate_code_ids_empty_response() {
+        assert_code_id_not_found(validate(|_| Ok(QueryCodeResponse::default())).await);
+    }
+
+    #[tokio::test]
+    async fn test_validate_code_ids_not_found_status() {
+        assert_code_id_not_found(
+            validate(|code_id| {
+                Err(tonic::Status::not_found(format!("no such code: {code_id}")).into())
+            })
+            .await,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_code_ids_invalid_argument_status() {
+        assert_code_id_not_found(
+            validate(|_| Err(tonic::Status::invalid_argument("code id").into())).await,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_code_ids_query_failure() {
+        let err = validate(|_| Err(tonic::Status::unavailable("connection refused").into()))
+            .await
+            .unwrap_err();
+
+        assert!(err.downcast_ref::<CodeIdNotFound>().is_none());
+        assert!(err.downcast_ref::<tonic::Status>().is_some());
+    }
+}
@@ -1,8 +1,8 @@
-use std::fs;
-
 use common::{artifacts_dir, NeutronStrategyConfig};
 use log::info;
 
+use crate::artifacts::write_atomic;
+
 const WRITE_OUTPUTS: &str = "WRITE_OUTPUTS";
 
 pub fn write_setup_artifacts(neutron_cfg: NeutronStrategyConfig) -> anyhow::Result<()> {
@@ -14,7 +14,7 @@ pub fn write_setup_artifacts(neutron_cfg: NeutronStrategyConfig) -> anyhow::Resu
     let target_path = artifacts_dir().join("neutron_strategy_config.toml");
     info!(target: WRITE_OUTPUTS, "writing neutron_strategy_config.toml to: {target_path:?}");
 
-    fs::write(target_path, neutron_cfg_toml)?;
+    write_atomic(&target_path, neutron_cfg_toml.as_bytes())?;
 
     Ok(())
 }